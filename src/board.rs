@@ -0,0 +1,248 @@
+//! A generic `N`×`N` bitboard with configurable win length `k`.
+//!
+//! Each mark's occupied cells are packed one-bit-per-cell (row-major) into a
+//! `u128`, which comfortably covers boards up to 11×11. Winning lines (every
+//! row, column, and diagonal run of length `k`) are enumerated once, at
+//! construction, into a lookup table shared (via `Rc`) by every position
+//! derived from that board, so playing a move never re-derives it;
+//! `calculate_winner` then just tests each line mask against the two
+//! bitboards. [`Game`](crate::Game) is still the fixed 3×3, `k = 3`
+//! tic-tac-toe board, which is exactly this engine constructed with
+//! `n = 3, k = 3` — [`Triple`](crate::Triple) names those eight lines for
+//! that specialization. Larger boards and other win lengths (e.g.
+//! Gomoku-style `n = 15, k = 5`) are reachable directly through [`Board`]
+//! and [`Cell`], without going through `Game`/`Square` at all.
+
+use std::rc::Rc;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::Mark;
+
+/// A cell on an `n`×`n` [`Board`], identified by its row-major linear
+/// index. [`Square`](crate::Square) is the named `n = 3` specialization of
+/// this index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell(pub usize);
+
+#[derive(Clone, Serialize)]
+pub struct Board {
+    n: usize,
+    k: usize,
+    #[serde(skip)]
+    lines: Rc<[u128]>,
+    xboard: u128,
+    oboard: u128,
+}
+
+impl Board {
+    /// Builds an empty `n`×`n` board whose winning lines are every row,
+    /// column, and diagonal run of length `k`.
+    pub fn new(n: usize, k: usize) -> Self {
+        Self {
+            n,
+            k,
+            lines: winning_lines(n, k).into(),
+            xboard: 0,
+            oboard: 0,
+        }
+    }
+
+    /// Builds a board directly from its bitboards, skipping `make_move`'s
+    /// occupancy checks. Used to reconstruct a board from a previously
+    /// validated position (e.g. a parsed notation string).
+    pub(crate) fn with_bits(n: usize, k: usize, xboard: u128, oboard: u128) -> Self {
+        Self {
+            n,
+            k,
+            lines: winning_lines(n, k).into(),
+            xboard,
+            oboard,
+        }
+    }
+
+    /// The board's side length, i.e. it has `n * n` cells.
+    pub(crate) fn n(&self) -> usize {
+        self.n
+    }
+
+    pub(crate) fn xboard(&self) -> u128 {
+        self.xboard
+    }
+
+    pub(crate) fn oboard(&self) -> u128 {
+        self.oboard
+    }
+
+    /// The winning line index and mark, if any line is fully covered by one
+    /// mark's bitboard.
+    pub fn calculate_winner(&self) -> Option<(Mark, usize)> {
+        for (idx, &mask) in self.lines.iter().enumerate() {
+            if self.xboard & mask == mask {
+                return Some((Mark::Cross, idx));
+            }
+            if self.oboard & mask == mask {
+                return Some((Mark::Naught, idx));
+            }
+        }
+        None
+    }
+
+    pub fn is_empty(&self, cell: Cell) -> bool {
+        debug_assert!(
+            cell.0 < self.n * self.n,
+            "cell {} is off the {}x{} board",
+            cell.0,
+            self.n,
+            self.n
+        );
+        (1u128 << cell.0) & (self.xboard | self.oboard) == 0
+    }
+
+    pub fn make_move(&self, mark: &Mark, cell: Cell) -> Result<Self, CellOccupied> {
+        if !self.is_empty(cell) {
+            return Err(CellOccupied);
+        }
+        let bit = 1u128 << cell.0;
+        Ok(match mark {
+            Mark::Cross => Self {
+                xboard: self.xboard | bit,
+                ..self.clone()
+            },
+            Mark::Naught => Self {
+                oboard: self.oboard | bit,
+                ..self.clone()
+            },
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Raw {
+            n: usize,
+            k: usize,
+            xboard: u128,
+            oboard: u128,
+        }
+
+        // `lines` is never trusted from the wire: it is always rebuilt from
+        // `n`/`k` so a hand-edited or cross-version save can't desync the
+        // winning-line table from the board it describes.
+        let raw = Raw::deserialize(deserializer)?;
+        Ok(Board::with_bits(raw.n, raw.k, raw.xboard, raw.oboard))
+    }
+}
+
+/// `cell` is already occupied by either mark.
+#[derive(Debug)]
+pub struct CellOccupied;
+
+/// Every winning line (row, column, or diagonal run of length `k`) on an
+/// `n`×`n` board, as a bitmask over row-major cell indices.
+fn winning_lines(n: usize, k: usize) -> Vec<u128> {
+    let idx = |r: usize, c: usize| (r * n + c) as u32;
+    let mut lines = Vec::new();
+
+    for r in 0..n {
+        for c0 in 0..=n - k {
+            lines.push((c0..c0 + k).fold(0u128, |mask, c| mask | (1 << idx(r, c))));
+        }
+    }
+    for c in 0..n {
+        for r0 in 0..=n - k {
+            lines.push((r0..r0 + k).fold(0u128, |mask, r| mask | (1 << idx(r, c))));
+        }
+    }
+    for r0 in 0..=n - k {
+        for c0 in 0..=n - k {
+            lines.push((0..k).fold(0u128, |mask, i| mask | (1 << idx(r0 + i, c0 + i))));
+        }
+    }
+    for r0 in 0..=n - k {
+        for c0 in k - 1..n {
+            lines.push((0..k).fold(0u128, |mask, i| mask | (1 << idx(r0 + i, c0 - i))));
+        }
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_winning_lines_count_for_3x3_k3() {
+        // 3 rows + 3 columns + 2 diagonals, matching `Triple`'s 8 variants.
+        assert_eq!(winning_lines(3, 3).len(), 8);
+    }
+
+    #[test]
+    fn test_winning_lines_count_for_4x4_k3() {
+        // (4 - 3 + 1) = 2 runs per row/column, plus 2x2 diagonal starts in
+        // each direction: 2*4 + 2*4 + 2*2 + 2*2 = 24.
+        assert_eq!(winning_lines(4, 3).len(), 24);
+    }
+
+    #[test]
+    fn test_calculate_winner_on_larger_board() {
+        let board = Board::new(4, 3);
+        let board = board.make_move(&Mark::Cross, Cell(0)).unwrap();
+        let board = board.make_move(&Mark::Cross, Cell(1)).unwrap();
+        let board = board.make_move(&Mark::Cross, Cell(2)).unwrap();
+
+        assert_eq!(board.calculate_winner(), Some((Mark::Cross, 0)));
+    }
+
+    #[test]
+    fn test_calculate_winner_none_when_no_line_is_complete() {
+        let board = Board::new(4, 3);
+        let board = board.make_move(&Mark::Cross, Cell(0)).unwrap();
+        let board = board.make_move(&Mark::Naught, Cell(1)).unwrap();
+
+        assert_eq!(board.calculate_winner(), None);
+    }
+
+    #[test]
+    fn test_make_move_rejects_occupied_cell() {
+        let board = Board::new(3, 3);
+        let board = board.make_move(&Mark::Cross, Cell(0)).unwrap();
+
+        assert!(board.make_move(&Mark::Naught, Cell(0)).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_recomputes_lines_from_n_and_k() {
+        // `lines` is never present on the wire (it's `#[serde(skip)]`), and a
+        // bogus value under that name must still be ignored rather than
+        // trusted, so deserializing the board below must recompute the same
+        // 4x4/k=3 line table `Board::new` would build, not whatever a
+        // mismatched n/k pair might have produced.
+        let json = r#"{"n":4,"k":3,"lines":["bogus"],"xboard":0,"oboard":0}"#;
+        let board: Board = serde_json::from_str(json).unwrap();
+
+        let board = board.make_move(&Mark::Cross, Cell(0)).unwrap();
+        let board = board.make_move(&Mark::Cross, Cell(1)).unwrap();
+        let board = board.make_move(&Mark::Cross, Cell(2)).unwrap();
+
+        assert_eq!(board.calculate_winner(), Some((Mark::Cross, 0)));
+    }
+
+    #[test]
+    fn test_display_renders_full_4x4_board() {
+        // Cell 15 is row 3, col 3 on a 4x4 board — out of range for a 3x3
+        // `Display` that only iterates 3 cells per row.
+        let board = Board::new(4, 3);
+        let board = board.make_move(&Mark::Cross, Cell(15)).unwrap();
+
+        assert_eq!(
+            board.to_string(),
+            "| | | | |\n| | | | |\n| | | | |\n| | | |X|"
+        );
+    }
+}
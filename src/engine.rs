@@ -0,0 +1,130 @@
+//! A perfect-play solver for [`Game`].
+//!
+//! Tic-tac-toe's game tree is tiny (at most `9!` positions), so a plain
+//! negamax search reaches every terminal position in well under a second.
+//! Alpha-beta pruning is added anyway to keep `best_move` fast enough to
+//! call interactively.
+
+use crate::{Game, GameStatus, Square};
+
+/// The raw magnitude of a won or lost position, before `negamax` adjusts it
+/// by ply depth to prefer faster wins and slower losses.
+const WIN_SCORE: i32 = 10;
+
+impl Game {
+    /// Returns the optimal move for the side to move, or `None` if the game
+    /// has already ended.
+    pub fn best_move(&self) -> Option<Square> {
+        if !matches!(self.status(), GameStatus::InProgress) {
+            return None;
+        }
+
+        let mut best_square = None;
+        let mut best_score = i32::MIN;
+
+        for square in self.legal_moves() {
+            let next = self.make_move(square).expect("square came from legal_moves");
+            let score = -negamax(&next, -WIN_SCORE - 1, WIN_SCORE + 1, 1);
+            if score > best_score {
+                best_score = score;
+                best_square = Some(square);
+            }
+        }
+
+        best_square
+    }
+}
+
+/// Negamax with alpha-beta pruning. `game` is the position to evaluate from
+/// the perspective of its side to move; `depth` is the ply count since the
+/// move that produced `best_move`'s root call, used to prefer faster wins
+/// and slower losses.
+fn negamax(game: &Game, mut alpha: i32, beta: i32, depth: i32) -> i32 {
+    match game.status() {
+        // The mark that just moved (i.e. not the side to move here) won,
+        // so this is always a loss for `game`'s side to move.
+        GameStatus::Won(_, _) => return -(WIN_SCORE - depth),
+        GameStatus::Draw => return 0,
+        GameStatus::InProgress => {}
+    }
+
+    let mut best = i32::MIN;
+    for square in game.legal_moves() {
+        let next = game.make_move(square).expect("square came from legal_moves");
+        let score = -negamax(&next, -beta, -alpha, depth + 1);
+        if score > best {
+            best = score;
+        }
+        if best > alpha {
+            alpha = best;
+        }
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Mark, Square};
+
+    #[test]
+    fn test_best_move_takes_immediate_win() {
+        // |X|O| |
+        // |X|O| |
+        // | | | |
+        let game = Game::default();
+        let game = game.make_move(Square::A1).unwrap();
+        let game = game.make_move(Square::A2).unwrap();
+        let game = game.make_move(Square::B1).unwrap();
+        let game = game.make_move(Square::B2).unwrap();
+
+        assert_eq!(game.best_move(), Some(Square::C1));
+    }
+
+    #[test]
+    fn test_best_move_blocks_opponent_win() {
+        // |O|O| |
+        // | |X| |
+        // | | |X|
+        let game = Game::default();
+        let game = game.make_move(Square::B2).unwrap();
+        let game = game.make_move(Square::A1).unwrap();
+        let game = game.make_move(Square::C3).unwrap();
+        let game = game.make_move(Square::A2).unwrap();
+
+        // Naught threatens A3 to complete RowA; Cross has no faster win of
+        // its own, so blocking is the only move that doesn't lose.
+        assert_eq!(game.best_move(), Some(Square::A3));
+    }
+
+    #[test]
+    fn test_best_move_none_once_game_has_ended() {
+        // | |O|X|
+        // |X|X|O|
+        // |X| |O|
+        let game = Game::default();
+        let game = game.make_move(Square::B2).unwrap();
+        let game = game.make_move(Square::A2).unwrap();
+        let game = game.make_move(Square::B1).unwrap();
+        let game = game.make_move(Square::B3).unwrap();
+        let game = game.make_move(Square::C1).unwrap();
+        let game = game.make_move(Square::C3).unwrap();
+        let game = game.make_move(Square::A3).unwrap();
+
+        assert_eq!(game.status(), crate::GameStatus::Won(Mark::Cross, crate::Triple::Diag2));
+        assert_eq!(game.best_move(), None);
+    }
+
+    #[test]
+    fn test_self_play_under_perfect_play_draws() {
+        let mut game = Game::default();
+        while let Some(square) = game.best_move() {
+            game = game.make_move(square).unwrap();
+        }
+
+        assert_eq!(game.status(), crate::GameStatus::Draw);
+    }
+}
@@ -1,12 +1,25 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+mod board;
+mod engine;
+mod session;
+
+pub use board::{Board, Cell};
+pub use session::Session;
+
 #[derive(Error, Debug)]
 pub enum ErrorKind {
     #[error("{0:?} is currently occupied")]
     IndexError(Square),
+    #[error("invalid position notation: {0:?}")]
+    InvalidNotation(String),
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Mark {
     Cross,
     Naught,
@@ -28,17 +41,49 @@ impl Mark {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// A cell on the 3×3 board, named `<row><column>`. Its discriminant is the
+/// cell's row-major linear index into the underlying [`Board`], i.e. the
+/// `N = 3` specialization of a generic bitboard cell index.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Square {
-    A1 = 0x80080080,
-    A2 = 0x40008000,
-    A3 = 0x20000808,
-    B1 = 0x08040000,
-    B2 = 0x04004044,
-    B3 = 0x02000400,
-    C1 = 0x00820002,
-    C2 = 0x00402000,
-    C3 = 0x00200220,
+    A1 = 0,
+    A2 = 1,
+    A3 = 2,
+    B1 = 3,
+    B2 = 4,
+    B3 = 5,
+    C1 = 6,
+    C2 = 7,
+    C3 = 8,
+}
+
+impl Square {
+    /// Every square on the board, in row-major order.
+    pub const ALL: [Square; 9] = [
+        Square::A1,
+        Square::A2,
+        Square::A3,
+        Square::B1,
+        Square::B2,
+        Square::B3,
+        Square::C1,
+        Square::C2,
+        Square::C3,
+    ];
+}
+
+impl From<Square> for Cell {
+    fn from(square: Square) -> Self {
+        Cell(square as usize)
+    }
+}
+
+/// The outcome of a [`Game`] at its current position.
+#[derive(Debug, PartialEq)]
+pub enum GameStatus {
+    InProgress,
+    Won(Mark, Triple),
+    Draw,
 }
 
 #[derive(Debug, PartialEq)]
@@ -69,86 +114,217 @@ impl From<u32> for Triple {
     }
 }
 
-#[derive(Clone, Copy)]
-struct Board {
-    xboard: u32,
-    oboard: u32,
-}
-
-impl Board {
-    pub fn new() -> Self {
-        Self {
-            xboard: 0x0,
-            oboard: 0x0,
-        }
-    }
-
-    pub fn calculate_winner(&self) -> Option<(Mark, Triple)> {
-        let xboard = self.xboard & (self.xboard << 1) & (self.xboard >> 1);
-        let oboard = self.oboard & (self.oboard << 1) & (self.oboard >> 1);
-        if xboard >= 1 {
-            Some((Mark::Cross, Triple::from(xboard.leading_zeros() - 1 >> 2)))
-        } else if oboard >= 1 {
-            Some((Mark::Naught, Triple::from(oboard.leading_zeros() - 1 >> 2)))
-        } else {
-            None
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.n();
+        for row in 0..n {
+            if row != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "|")?;
+            for col in 0..n {
+                let bit = 1u128 << (row * n + col);
+                let cell = if (self.xboard() & bit) != 0 {
+                    Mark::Cross.to_str()
+                } else if (self.oboard() & bit) != 0 {
+                    Mark::Naught.to_str()
+                } else {
+                    " "
+                };
+                write!(f, "{cell}|")?;
+            }
         }
-    }
-
-    fn check_index(&self, square: &Square) -> Result<(), ErrorKind> {
-        if (*square as u32 & (self.xboard | self.oboard)) == 0 {
-            Ok(())
-        } else {
-            Err(ErrorKind::IndexError(*square))
-        }
-    }
-
-    pub fn make_move(&self, mark: &Mark, square: Square) -> Result<Self, ErrorKind> {
-        self.check_index(&square)?;
-        Ok(match mark {
-            Mark::Cross => Self {
-                xboard: self.xboard | square as u32,
-                oboard: self.oboard,
-            },
-            Mark::Naught => Self {
-                xboard: self.xboard,
-                oboard: self.oboard | square as u32,
-            },
-        })
+        Ok(())
     }
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct Game {
     current_mark: Mark,
     board: Board,
+    actions: Vec<Square>,
+    /// The position `actions` have been replayed on top of. Equal to
+    /// `board` itself for a game with no history yet — including one
+    /// reconstructed via `FromStr`, whose `actions` are always empty — so
+    /// `undo` always has a real starting position to rewind to instead of
+    /// assuming a blank board.
+    base: Board,
 }
 
 impl Game {
     pub fn new(starting_mark: Mark) -> Game {
+        let board = Board::new(3, 3);
         Game {
             current_mark: starting_mark,
-            board: Board::new(),
+            board: board.clone(),
+            actions: Vec::new(),
+            base: board,
         }
     }
 
     pub fn make_move(&self, square: Square) -> Result<Self, ErrorKind> {
-        let current_state = self.board.make_move(&self.current_mark, square)?;
+        let current_state = self
+            .board
+            .make_move(&self.current_mark, square.into())
+            .map_err(|_| ErrorKind::IndexError(square))?;
+        let mut actions = self.actions.clone();
+        actions.push(square);
         Ok(Game {
             current_mark: self.current_mark.other(),
             board: current_state,
+            actions,
+            base: self.base.clone(),
         })
     }
 
+    /// The moves played so far, in order, relative to `base`.
+    pub fn history(&self) -> &[Square] {
+        &self.actions
+    }
+
+    /// Reconstructs the position before the last move, or `None` if no
+    /// moves have been played since `base`.
+    pub fn undo(&self) -> Option<Self> {
+        if self.actions.is_empty() {
+            return None;
+        }
+
+        let mut starting_mark = self.current_mark;
+        if !self.actions.len().is_multiple_of(2) {
+            starting_mark = starting_mark.other();
+        }
+
+        let mut game = Game {
+            current_mark: starting_mark,
+            board: self.base.clone(),
+            actions: Vec::new(),
+            base: self.base.clone(),
+        };
+        for &square in &self.actions[..self.actions.len() - 1] {
+            game = game
+                .make_move(square)
+                .expect("history only contains previously legal moves");
+        }
+        Some(game)
+    }
+
     pub fn calculate_winner(&self) -> Option<(Mark, Triple)> {
-        self.board.calculate_winner()
+        self.board
+            .calculate_winner()
+            .map(|(mark, line)| (mark, Triple::from(line as u32)))
+    }
+
+    /// Every square that is not yet occupied by either mark.
+    pub fn legal_moves(&self) -> impl Iterator<Item = Square> + '_ {
+        Square::ALL
+            .into_iter()
+            .filter(move |&square| self.board.is_empty(square.into()))
+    }
+
+    /// The result of the game so far: in progress, won, or drawn.
+    pub fn status(&self) -> GameStatus {
+        if let Some((mark, triple)) = self.calculate_winner() {
+            GameStatus::Won(mark, triple)
+        } else if self.legal_moves().next().is_none() {
+            GameStatus::Draw
+        } else {
+            GameStatus::InProgress
+        }
+    }
+
+    /// Renders the position as a compact, round-trippable notation: the
+    /// nine cells in row-major order (`X`, `O`, or `.`), rows separated by
+    /// `/`, followed by the side to move, e.g. `.OX/XXO/X.O X`.
+    pub fn notation(&self) -> String {
+        let mut out = String::with_capacity(11);
+        for (i, square) in Square::ALL.into_iter().enumerate() {
+            if i != 0 && i % 3 == 0 {
+                out.push('/');
+            }
+            let bit = 1u128 << square as usize;
+            out.push(if (self.board.xboard() & bit) != 0 {
+                'X'
+            } else if (self.board.oboard() & bit) != 0 {
+                'O'
+            } else {
+                '.'
+            });
+        }
+        out.push(' ');
+        out.push_str(self.current_mark.to_str());
+        out
+    }
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.board)
+    }
+}
+
+impl FromStr for Game {
+    type Err = ErrorKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ErrorKind::InvalidNotation(s.to_string());
+
+        let (board_part, mark_part) = s.split_once(' ').ok_or_else(invalid)?;
+        let rows: Vec<&str> = board_part.split('/').collect();
+        if rows.len() != 3 || rows.iter().any(|row| row.len() != 3) {
+            return Err(invalid());
+        }
+        let cells: Vec<char> = rows.iter().flat_map(|row| row.chars()).collect();
+
+        let mut xboard = 0u128;
+        let mut oboard = 0u128;
+        let mut x_count = 0u32;
+        let mut o_count = 0u32;
+        for (square, cell) in Square::ALL.into_iter().zip(cells) {
+            let bit = 1u128 << square as usize;
+            match cell {
+                'X' => {
+                    xboard |= bit;
+                    x_count += 1;
+                }
+                'O' => {
+                    oboard |= bit;
+                    o_count += 1;
+                }
+                '.' => {}
+                _ => return Err(invalid()),
+            }
+        }
+        if x_count.abs_diff(o_count) > 1 {
+            return Err(invalid());
+        }
+
+        let current_mark = match mark_part {
+            "X" => Mark::Cross,
+            "O" => Mark::Naught,
+            _ => return Err(invalid()),
+        };
+
+        // The notation only encodes the final position, not the moves that
+        // produced it, so a parsed game starts with empty history — and
+        // that position becomes `base`, since there's nothing before it.
+        let board = Board::with_bits(3, 3, xboard, oboard);
+        Ok(Game {
+            current_mark,
+            board: board.clone(),
+            actions: Vec::new(),
+            base: board,
+        })
     }
 }
 
 impl Default for Game {
     fn default() -> Game {
+        let board = Board::new(3, 3);
         Game {
             current_mark: Mark::Cross,
-            board: Board::new(),
+            board: board.clone(),
+            actions: Vec::new(),
+            base: board,
         }
     }
 }
@@ -192,4 +368,155 @@ mod tests {
         let game = game.make_move(Square::B2);
         assert!(game.is_err());
     }
+
+    #[test]
+    fn test_legal_moves_shrinks_as_squares_fill() {
+        let game = Game::default();
+        assert_eq!(game.legal_moves().count(), 9);
+
+        let game = game.make_move(Square::A1).unwrap();
+        assert_eq!(game.legal_moves().count(), 8);
+        assert!(!game.legal_moves().any(|square| square == Square::A1));
+    }
+
+    #[test]
+    fn test_status_in_progress() {
+        let game = Game::default();
+        assert_eq!(game.status(), GameStatus::InProgress);
+    }
+
+    #[test]
+    fn test_status_won() {
+        // |X|O| |
+        // |X|O| |
+        // |X| | |
+        let game = Game::default();
+        let game = game.make_move(Square::A1).unwrap();
+        let game = game.make_move(Square::A2).unwrap();
+        let game = game.make_move(Square::B1).unwrap();
+        let game = game.make_move(Square::B2).unwrap();
+        let game = game.make_move(Square::C1).unwrap();
+
+        assert_eq!(game.status(), GameStatus::Won(Mark::Cross, Triple::Col1));
+    }
+
+    #[test]
+    fn test_status_draw() {
+        // |X|O|X|
+        // |X|O|O|
+        // |O|X|X|
+        let game = Game::default();
+        let game = game.make_move(Square::A1).unwrap();
+        let game = game.make_move(Square::A2).unwrap();
+        let game = game.make_move(Square::A3).unwrap();
+        let game = game.make_move(Square::B2).unwrap();
+        let game = game.make_move(Square::B1).unwrap();
+        let game = game.make_move(Square::B3).unwrap();
+        let game = game.make_move(Square::C2).unwrap();
+        let game = game.make_move(Square::C1).unwrap();
+        let game = game.make_move(Square::C3).unwrap();
+
+        assert_eq!(game.status(), GameStatus::Draw);
+    }
+
+    #[test]
+    fn test_notation_round_trips_through_from_str() {
+        let game = Game::default();
+        let game = game.make_move(Square::B2).unwrap();
+        let game = game.make_move(Square::A1).unwrap();
+
+        let notation = game.notation();
+        assert_eq!(notation, "O../.X./... X");
+
+        let parsed: Game = notation.parse().unwrap();
+        assert_eq!(parsed.notation(), notation);
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let game = Game::default();
+        let game = game.make_move(Square::B2).unwrap();
+
+        let json = serde_json::to_string(&game).unwrap();
+        let restored: Game = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.notation(), game.notation());
+    }
+
+    #[test]
+    fn test_from_str_rejects_missing_slashes() {
+        let result: Result<Game, _> = "XXXOOO... X".parse();
+        assert!(matches!(result, Err(ErrorKind::InvalidNotation(_))));
+    }
+
+    #[test]
+    fn test_from_str_rejects_misplaced_slash() {
+        let result: Result<Game, _> = "X/XXOOO.. X".parse();
+        assert!(matches!(result, Err(ErrorKind::InvalidNotation(_))));
+    }
+
+    #[test]
+    fn test_from_str_rejects_invalid_mark() {
+        let result: Result<Game, _> = ".../.../... Z".parse();
+        assert!(matches!(result, Err(ErrorKind::InvalidNotation(_))));
+    }
+
+    #[test]
+    fn test_from_str_rejects_unbalanced_counts() {
+        let result: Result<Game, _> = "XXX/XX./... O".parse();
+        assert!(matches!(result, Err(ErrorKind::InvalidNotation(_))));
+    }
+
+    #[test]
+    fn test_display_empty_board() {
+        let game = Game::default();
+        assert_eq!(game.to_string(), "| | | |\n| | | |\n| | | |");
+    }
+
+    #[test]
+    fn test_display_shows_played_moves() {
+        let game = Game::default();
+        let game = game.make_move(Square::A1).unwrap();
+        let game = game.make_move(Square::B2).unwrap();
+
+        assert_eq!(game.to_string(), "|X| | |\n| |O| |\n| | | |");
+        assert_eq!(game.to_string(), game.board.to_string());
+    }
+
+    #[test]
+    fn test_history_tracks_moves_in_order() {
+        let game = Game::default();
+        let game = game.make_move(Square::A1).unwrap();
+        let game = game.make_move(Square::B2).unwrap();
+
+        assert_eq!(game.history(), [Square::A1, Square::B2]);
+    }
+
+    #[test]
+    fn test_undo_restores_previous_position() {
+        let game = Game::default();
+        let after_first = game.make_move(Square::A1).unwrap();
+        let after_second = after_first.make_move(Square::B2).unwrap();
+
+        let undone = after_second.undo().unwrap();
+        assert_eq!(undone.history(), after_first.history());
+        assert_eq!(undone.notation(), after_first.notation());
+    }
+
+    #[test]
+    fn test_undo_on_fresh_game_is_none() {
+        let game = Game::default();
+        assert!(game.undo().is_none());
+    }
+
+    #[test]
+    fn test_undo_on_parsed_game_restores_parsed_position() {
+        let notation = "XOX/OXO/... X";
+        let game: Game = notation.parse().unwrap();
+
+        let after_move = game.make_move(Square::C1).unwrap();
+        let undone = after_move.undo().unwrap();
+
+        assert_eq!(undone.notation(), notation);
+    }
 }
@@ -0,0 +1,102 @@
+//! A scoreboard across successive [`Game`]s.
+
+use crate::{Game, GameStatus, Mark};
+
+/// Owns the current [`Game`] and accumulates wins, losses, and draws across
+/// the rounds played in it.
+pub struct Session {
+    game: Game,
+    cross_wins: u32,
+    naught_wins: u32,
+    draws: u32,
+    next_starting_mark: Mark,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self {
+            game: Game::new(Mark::Cross),
+            cross_wins: 0,
+            naught_wins: 0,
+            draws: 0,
+            next_starting_mark: Mark::Cross,
+        }
+    }
+
+    /// Starts a fresh game, alternating which mark moves first, and returns
+    /// a handle to it.
+    pub fn start_game(&mut self) -> &mut Game {
+        self.game = Game::new(self.next_starting_mark);
+        self.next_starting_mark = self.next_starting_mark.other();
+        &mut self.game
+    }
+
+    /// Records the outcome of the current game into the tally. Has no
+    /// effect if the game is still in progress.
+    ///
+    /// Callers must call this exactly once per game, after it ends and
+    /// before the next `start_game`; calling it more than once for the
+    /// same finished game double-counts its result.
+    pub fn record_result(&mut self) {
+        match self.game.status() {
+            GameStatus::Won(Mark::Cross, _) => self.cross_wins += 1,
+            GameStatus::Won(Mark::Naught, _) => self.naught_wins += 1,
+            GameStatus::Draw => self.draws += 1,
+            GameStatus::InProgress => {}
+        }
+    }
+
+    /// The running tally as `(cross wins, naught wins, draws)`.
+    pub fn scores(&self) -> (u32, u32, u32) {
+        (self.cross_wins, self.naught_wins, self.draws)
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Square;
+
+    #[test]
+    fn test_first_game_starts_with_cross() {
+        let mut session = Session::new();
+        let game = session.start_game();
+        assert_eq!(game.notation().split_once(' ').unwrap().1, "X");
+    }
+
+    #[test]
+    fn test_start_game_alternates_starting_mark() {
+        let mut session = Session::new();
+        session.start_game();
+        let second = session.start_game();
+        assert_eq!(second.notation().split_once(' ').unwrap().1, "O");
+    }
+
+    #[test]
+    fn test_record_result_tallies_cross_win() {
+        let mut session = Session::new();
+        let game = session.start_game();
+        *game = game.make_move(Square::A1).unwrap(); // X
+        *game = game.make_move(Square::A2).unwrap(); // O
+        *game = game.make_move(Square::B1).unwrap(); // X
+        *game = game.make_move(Square::A3).unwrap(); // O
+        *game = game.make_move(Square::C1).unwrap(); // X, wins Col1
+
+        session.record_result();
+        assert_eq!(session.scores(), (1, 0, 0));
+    }
+
+    #[test]
+    fn test_record_result_ignores_in_progress_game() {
+        let mut session = Session::new();
+        session.start_game();
+        session.record_result();
+        assert_eq!(session.scores(), (0, 0, 0));
+    }
+}